@@ -0,0 +1,116 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DataEnum, DataStruct, DeriveInput, Fields};
+
+/// Derives `arma_rs::ToArma` for a struct or enum.
+///
+/// Structs must have named fields; each field is emitted under its
+/// snake_case name unless overridden with `#[arma(rename = "...")]`, or
+/// dropped entirely with `#[arma(skip)]`.
+///
+/// Enums may only contain unit variants (emitted as an empty object) or
+/// single-field tuple variants (emitted by delegating to the inner
+/// value's own `to_arma`), which matches how `Data` dispatches to its
+/// payload types.
+#[proc_macro_derive(ToArma, attributes(arma))]
+pub fn derive_to_arma(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let body = match &input.data {
+        Data::Struct(data) => derive_struct(data),
+        Data::Enum(data) => derive_enum(data),
+        Data::Union(_) => panic!("ToArma cannot be derived for unions"),
+    };
+
+    let expanded = quote! {
+        impl ::arma_rs::ToArma for #name {
+            fn to_arma(&self) -> ::arma_rs::ArmaValue {
+                #body
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+struct FieldAttrs {
+    rename: Option<String>,
+    skip: bool,
+}
+
+fn field_attrs(field: &syn::Field) -> FieldAttrs {
+    let mut attrs = FieldAttrs { rename: None, skip: false };
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("arma") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                attrs.skip = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("rename") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                attrs.rename = Some(value.value());
+                return Ok(());
+            }
+
+            Err(meta.error("unsupported #[arma(...)] attribute"))
+        })
+        .expect("invalid #[arma(...)] attribute");
+    }
+
+    attrs
+}
+
+fn derive_struct(data: &DataStruct) -> proc_macro2::TokenStream {
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        _ => panic!("ToArma can only be derived for structs with named fields"),
+    };
+
+    let entries = fields.iter().filter_map(|field| {
+        let attrs = field_attrs(field);
+        if attrs.skip {
+            return None;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let key = attrs.rename.unwrap_or_else(|| ident.to_string());
+        let key = syn::LitStr::new(&key, ident.span());
+
+        Some(quote! { #key: self.#ident })
+    });
+
+    quote! {
+        ::arma_rs::arma_value!({ #(#entries),* })
+    }
+}
+
+fn derive_enum(data: &DataEnum) -> proc_macro2::TokenStream {
+    let arms = data.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+
+        match &variant.fields {
+            Fields::Unit => quote! {
+                Self::#variant_ident => ::arma_rs::arma_value!({})
+            },
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                Self::#variant_ident(inner) => ::arma_rs::ToArma::to_arma(inner)
+            },
+            _ => panic!(
+                "ToArma can only be derived for enums with unit or single-field tuple variants"
+            ),
+        }
+    });
+
+    quote! {
+        match self {
+            #(#arms),*
+        }
+    }
+}
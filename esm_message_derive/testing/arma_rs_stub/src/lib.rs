@@ -0,0 +1,36 @@
+//! Minimal stand-in for the real `arma_rs` crate's `ToArma`/`ArmaValue`
+//! surface, covering only what `esm_message_derive`'s own tests need to
+//! exercise derive macro expansion end-to-end.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArmaValue {
+    Nil,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<ArmaValue>),
+    Object(Vec<(String, ArmaValue)>),
+}
+
+pub trait ToArma {
+    fn to_arma(&self) -> ArmaValue;
+}
+
+impl ToArma for String {
+    fn to_arma(&self) -> ArmaValue {
+        ArmaValue::String(self.clone())
+    }
+}
+
+impl ToArma for i64 {
+    fn to_arma(&self) -> ArmaValue {
+        ArmaValue::Number(*self as f64)
+    }
+}
+
+#[macro_export]
+macro_rules! arma_value {
+    ({ $($key:literal : $val:expr),* $(,)? }) => {{
+        $crate::ArmaValue::Object(vec![$(($key.to_string(), $crate::ToArma::to_arma(&$val))),*])
+    }};
+}
@@ -0,0 +1,27 @@
+use arma_rs::ToArma;
+use esm_message_derive::ToArma;
+
+#[derive(ToArma)]
+struct Example {
+    #[arma(rename = "renamed_field")]
+    original_name: String,
+    #[arma(skip)]
+    #[allow(dead_code)]
+    internal_only: String,
+    kept: i64,
+}
+
+#[test]
+fn test_rename_and_skip() {
+    let example =
+        Example { original_name: "hello".to_string(), internal_only: "ignored".to_string(), kept: 42 };
+
+    let rendered = format!("{:?}", example.to_arma());
+
+    assert!(rendered.contains("renamed_field"));
+    assert!(rendered.contains("hello"));
+    assert!(rendered.contains("kept"));
+    assert!(!rendered.contains("original_name"));
+    assert!(!rendered.contains("internal_only"));
+    assert!(!rendered.contains("ignored"));
+}
@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use arma_rs::{ArmaValue, ToArma, arma_value};
 use chrono::{DateTime, Utc};
+use esm_message_derive::ToArma;
 use serde::{Deserialize, Serialize};
 
 /// Attempts to retrieve a reference to the data. Panicking if the internal data does not match the provided type.
@@ -21,7 +22,7 @@ macro_rules! retrieve_data {
 
 
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
 #[serde(tag = "type", content = "content", rename_all = "snake_case")]
 pub enum Data {
     Empty,
@@ -29,6 +30,9 @@ pub enum Data {
     Init(Init),
     PostInit(PostInit),
     Query(Query),
+    Reward(Reward),
+    Leaderboard(Leaderboard),
+    RewardRoll(RewardRoll),
 }
 
 impl Default for Data {
@@ -37,28 +41,12 @@ impl Default for Data {
     }
 }
 
-impl ToArma for Data {
-    fn to_arma(&self) -> ArmaValue {
-        match self {
-            Data::Empty => arma_value!({}),
-            d => d.to_arma()
-        }
-    }
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
 pub struct Test {
     pub foo: String
 }
 
-impl ToArma for Test {
-    fn to_arma(&self) -> ArmaValue {
-        arma_value!({ "foo": self.foo })
-    }
-}
-
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
 pub struct Init {
     pub server_name: String,
     pub price_per_object: f32,
@@ -68,29 +56,106 @@ pub struct Init {
     pub extension_version: String,
 }
 
-// TODO: Create derive for ToArma so this isn't needed
-impl ToArma for Init {
+/// One segment of a pity curve: starting at `start_pity` pulls since the
+/// last win, the chance is `start_chance_percent`, climbing by
+/// `increment_percent` for each further failed pull until the next point
+/// (or 100%) takes over.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProbabilityPoint {
+    pub start_pity: u32,
+    pub start_chance_percent: f64,
+    pub increment_percent: f64,
+}
+
+/// A pity/soft-guarantee curve for gambling and reward rolls. Configured
+/// with an ordered list of [`ProbabilityPoint`]s; call [`Self::post_configure`]
+/// once after loading to precompute [`Self::chance_at`]'s lookup table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ProbabilityModel {
+    pub points: Vec<ProbabilityPoint>,
+    pub clear_status_on_higher_rarity_pulled: bool,
+    pub maximum_guarantee_pity: Option<u32>,
+
+    #[serde(default)]
+    probability_percents: Vec<f64>,
+}
+
+/// Hard ceiling on precomputed pity steps. Guards against a misconfigured
+/// curve (e.g. a trailing point whose `increment_percent` is `0.0`) that
+/// would otherwise never reach 100% and loop forever; a curve that hasn't
+/// converged by this pity count is clamped to a guarantee here instead.
+const MAX_PITY_STEPS: u32 = 10_000;
+
+impl ProbabilityModel {
+    /// Walks `points` in order, filling `probability_percents` at each
+    /// pity count with the active point's base chance plus increments
+    /// (clamped to 100%), stopping once the chance reaches 100% or
+    /// [`MAX_PITY_STEPS`] is hit, whichever comes first.
+    pub fn post_configure(&mut self) {
+        let mut percents = Vec::new();
+        let mut point_index = 0;
+        let mut pity = 0u32;
+
+        loop {
+            while point_index + 1 < self.points.len() && self.points[point_index + 1].start_pity <= pity {
+                point_index += 1;
+            }
+
+            let Some(point) = self.points.get(point_index) else {
+                break;
+            };
+
+            let steps = pity.saturating_sub(point.start_pity) as f64;
+            let mut percent = (point.start_chance_percent + point.increment_percent * steps).min(100.0);
+
+            if pity >= MAX_PITY_STEPS {
+                percent = 100.0;
+            }
+
+            percents.push(percent);
+
+            if percent >= 100.0 {
+                break;
+            }
+
+            pity += 1;
+        }
+
+        self.probability_percents = percents;
+    }
+
+    /// The chance, as a percentage, of a win at the given pity count.
+    pub fn chance_at(&self, pity: u32) -> f64 {
+        self.probability_percents.get(pity as usize).copied().unwrap_or(100.0)
+    }
+
+    /// The pity count at which a win is guaranteed. Falls back to the
+    /// precomputed table's last index, but `maximum_guarantee_pity` can
+    /// override it for display when the real guarantee sits lower.
+    pub fn maximum_guarantee(&self) -> u32 {
+        self.maximum_guarantee_pity
+            .unwrap_or_else(|| self.probability_percents.len().saturating_sub(1) as u32)
+    }
+}
+
+impl ToArma for ProbabilityModel {
     fn to_arma(&self) -> ArmaValue {
         arma_value!({
-            "server_name": self.server_name,
-            "price_per_object": self.price_per_object,
-            "territory_lifetime": self.territory_lifetime,
-            "territory_data": self.territory_data,
-            "server_start_time": self.server_start_time,
-            "extension_version": self.extension_version
+            "probability_percents": self.probability_percents.clone(),
+            "maximum_guarantee": self.maximum_guarantee()
         })
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
 pub struct PostInit {
     pub extdb_path: String,
     pub gambling_modifier: i64,
     pub gambling_payout: i64,
+    pub gambling_probability: ProbabilityModel,
     pub gambling_randomizer_max: f64,
     pub gambling_randomizer_mid: f64,
     pub gambling_randomizer_min: f64,
-    pub gambling_win_chance: i64,
     pub logging_add_player_to_territory: bool,
     pub logging_demote_player: bool,
     pub logging_exec: bool,
@@ -106,87 +171,299 @@ pub struct PostInit {
     pub territory_payment_tax: i64,
     pub territory_upgrade_tax: i64,
     pub territory_admins: Vec<String>,
-    // For now
-    // pub reward_player_poptabs: i64,
-    // pub reward_locker_poptabs: i64,
-    // pub reward_respect: i64,
-    // pub reward_items: HashMap<String, i64>,
 }
 
-impl ToArma for PostInit {
+/// A bundle of everything a player can be rewarded: currency, locker
+/// currency, respect, and arbitrary items. `items` has no native Arma map
+/// type, so it is serialized as an array of `[classname, count]` pairs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Reward {
+    pub player_poptabs: i64,
+    pub locker_poptabs: i64,
+    pub respect: i64,
+    pub items: HashMap<String, i64>,
+}
+
+impl ToArma for Reward {
     fn to_arma(&self) -> ArmaValue {
-        arma_value!({
-            "extdb_path": self.extdb_path,
-            "gambling_modifier": self.gambling_modifier,
-            "gambling_payout": self.gambling_payout,
-            "gambling_randomizer_max": self.gambling_randomizer_max,
-            "gambling_randomizer_mid": self.gambling_randomizer_mid,
-            "gambling_randomizer_min": self.gambling_randomizer_min,
-            "gambling_win_chance": self.gambling_win_chance,
-            "logging_add_player_to_territory": self.logging_add_player_to_territory,
-            "logging_demote_player": self.logging_demote_player,
-            "logging_exec": self.logging_exec,
-            "logging_gamble": self.logging_gamble,
-            "logging_modify_player": self.logging_modify_player,
-            "logging_pay_territory": self.logging_pay_territory,
-            "logging_promote_player": self.logging_promote_player,
-            "logging_remove_player_from_territory": self.logging_remove_player_from_territory,
-            "logging_reward": self.logging_reward,
-            "logging_transfer": self.logging_transfer,
-            "logging_upgrade_territory": self.logging_upgrade_territory,
-            "max_payment_count": self.max_payment_count,
-            "territory_payment_tax": self.territory_payment_tax,
-            "territory_upgrade_tax": self.territory_upgrade_tax,
-            "territory_admins": self.territory_admins
-        })
+        let items = self
+            .items
+            .iter()
+            .map(|(classname, count)| ArmaValue::Array(vec![classname.to_arma(), count.to_arma()]))
+            .collect::<Vec<ArmaValue>>();
+
+        ArmaValue::Object(vec![
+            ("player_poptabs".into(), self.player_poptabs.to_arma()),
+            ("locker_poptabs".into(), self.locker_poptabs.to_arma()),
+            ("respect".into(), self.respect.to_arma()),
+            ("items".into(), ArmaValue::Array(items)),
+        ])
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// The outcome of a single pity-governed reward roll, carrying the pity
+/// count it was rolled at and the model so SQF can display odds without
+/// re-deriving them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
+pub struct RewardRoll {
+    pub pity: u32,
+    pub won: bool,
+    pub probability: ProbabilityModel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
 pub struct Event {
     pub event_type: String,
     pub triggered_at: DateTime<Utc>
 }
 
-impl ToArma for Event {
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
+pub struct TerritoryArguments {
+    pub territory_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
+pub struct TerritoriesArguments {
+    pub uid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
+pub struct GetTerritoryIdFromHashArguments {
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
+pub struct SetCustomTerritoryIdArguments {
+    pub territory_id: String,
+    pub custom_territory_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToArma)]
+pub struct GetHashFromIdArguments {
+    pub territory_id: String,
+}
+
+/// Every query the server understands, paired with its own typed
+/// arguments rather than a bare string map, so a typo in a query name or
+/// a missing argument is caught at construction instead of reaching the
+/// database layer.
+///
+/// - `Territory`: Returns a single territory that matches `territory_id`
+/// - `Territories`: Returns any territories `uid` is a part of, or lists all territories if `uid` is absent
+/// - `PlayerInfoAccountOnly`
+/// - `Leaderboard` / `LeaderboardDeaths` / `LeaderboardScore`
+/// - `Restore` / `ResetPlayer` / `ResetAll`
+/// - `GetTerritoryIdFromHash`
+/// - `SetCustomTerritoryId`
+/// - `GetHashFromId`
+/// - `GetPaymentCount` / `IncrementPaymentCounter` / `ResetPaymentCounter`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "name", content = "arguments", rename_all = "snake_case")]
+pub enum QueryName {
+    Territory(TerritoryArguments),
+    Territories(TerritoriesArguments),
+    PlayerInfoAccountOnly,
+    Leaderboard,
+    LeaderboardDeaths,
+    LeaderboardScore,
+    Restore,
+    ResetPlayer,
+    ResetAll,
+    GetTerritoryIdFromHash(GetTerritoryIdFromHashArguments),
+    SetCustomTerritoryId(SetCustomTerritoryIdArguments),
+    GetHashFromId(GetHashFromIdArguments),
+    GetPaymentCount,
+    IncrementPaymentCounter,
+    ResetPaymentCounter,
+}
+
+/// Why a raw `(name, arguments)` pair failed to convert into a [`QueryName`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryValidationError {
+    UnknownQueryName(String),
+    MissingArgument { query: String, argument: String },
+    UnexpectedArgument { query: String, argument: String },
+}
+
+impl QueryName {
+    /// Converts a legacy `{ name, arguments }` pair into a typed
+    /// `QueryName`, rejecting unknown query names and missing or
+    /// unexpected arguments.
+    pub fn validate(name: &str, mut arguments: HashMap<String, String>) -> Result<QueryName, QueryValidationError> {
+        macro_rules! require {
+            ($key:literal) => {
+                arguments.remove($key).ok_or_else(|| QueryValidationError::MissingArgument {
+                    query: name.to_string(),
+                    argument: $key.to_string(),
+                })?
+            };
+        }
+
+        macro_rules! reject_extra {
+            () => {
+                if let Some(argument) = arguments.into_keys().next() {
+                    return Err(QueryValidationError::UnexpectedArgument { query: name.to_string(), argument });
+                }
+            };
+        }
+
+        let query_name = match name {
+            "territory" => {
+                let territory_id = require!("territory_id");
+                reject_extra!();
+                QueryName::Territory(TerritoryArguments { territory_id })
+            }
+            "territories" => {
+                let uid = arguments.remove("uid");
+                reject_extra!();
+                QueryName::Territories(TerritoriesArguments { uid })
+            }
+            "player_info_account_only" => {
+                reject_extra!();
+                QueryName::PlayerInfoAccountOnly
+            }
+            "leaderboard" => {
+                reject_extra!();
+                QueryName::Leaderboard
+            }
+            "leaderboard_deaths" => {
+                reject_extra!();
+                QueryName::LeaderboardDeaths
+            }
+            "leaderboard_score" => {
+                reject_extra!();
+                QueryName::LeaderboardScore
+            }
+            "restore" => {
+                reject_extra!();
+                QueryName::Restore
+            }
+            "reset_player" => {
+                reject_extra!();
+                QueryName::ResetPlayer
+            }
+            "reset_all" => {
+                reject_extra!();
+                QueryName::ResetAll
+            }
+            "get_territory_id_from_hash" => {
+                let hash = require!("hash");
+                reject_extra!();
+                QueryName::GetTerritoryIdFromHash(GetTerritoryIdFromHashArguments { hash })
+            }
+            "set_custom_territory_id" => {
+                let territory_id = require!("territory_id");
+                let custom_territory_id = require!("custom_territory_id");
+                reject_extra!();
+                QueryName::SetCustomTerritoryId(SetCustomTerritoryIdArguments { territory_id, custom_territory_id })
+            }
+            "get_hash_from_id" => {
+                let territory_id = require!("territory_id");
+                reject_extra!();
+                QueryName::GetHashFromId(GetHashFromIdArguments { territory_id })
+            }
+            "get_payment_count" => {
+                reject_extra!();
+                QueryName::GetPaymentCount
+            }
+            "increment_payment_counter" => {
+                reject_extra!();
+                QueryName::IncrementPaymentCounter
+            }
+            "reset_payment_counter" => {
+                reject_extra!();
+                QueryName::ResetPaymentCounter
+            }
+            _ => return Err(QueryValidationError::UnknownQueryName(name.to_string())),
+        };
+
+        Ok(query_name)
+    }
+}
+
+impl ToArma for QueryName {
     fn to_arma(&self) -> ArmaValue {
-        arma_value!({
-            "event_type": self.event_type,
-            "triggered_at": self.triggered_at
-        })
+        let (name, arguments) = match self {
+            QueryName::Territory(args) => ("territory", args.to_arma()),
+            QueryName::Territories(args) => ("territories", args.to_arma()),
+            QueryName::PlayerInfoAccountOnly => ("player_info_account_only", arma_value!({})),
+            QueryName::Leaderboard => ("leaderboard", arma_value!({})),
+            QueryName::LeaderboardDeaths => ("leaderboard_deaths", arma_value!({})),
+            QueryName::LeaderboardScore => ("leaderboard_score", arma_value!({})),
+            QueryName::Restore => ("restore", arma_value!({})),
+            QueryName::ResetPlayer => ("reset_player", arma_value!({})),
+            QueryName::ResetAll => ("reset_all", arma_value!({})),
+            QueryName::GetTerritoryIdFromHash(args) => ("get_territory_id_from_hash", args.to_arma()),
+            QueryName::SetCustomTerritoryId(args) => ("set_custom_territory_id", args.to_arma()),
+            QueryName::GetHashFromId(args) => ("get_hash_from_id", args.to_arma()),
+            QueryName::GetPaymentCount => ("get_payment_count", arma_value!({})),
+            QueryName::IncrementPaymentCounter => ("increment_payment_counter", arma_value!({})),
+            QueryName::ResetPaymentCounter => ("reset_payment_counter", arma_value!({})),
+        };
+
+        ArmaValue::Object(vec![("name".into(), ArmaValue::String(name.to_string())), ("arguments".into(), arguments)])
     }
 }
 
-// territory
-//   - territory_id: Returns a single territory that matches this ID
-// territories:
-//   - uid: Returns any territories the target uid is a part of
-//   - (no arguments): Lists all territories
-// player_info_account_only
-// leaderboard
-// leaderboard_deaths
-// leaderboard_score
-// restore
-// reset_player
-// reset_all
-// get_territory_id_from_hash
-// set_custom_territory_id
-// get_hash_from_id
-// get_payment_count
-// increment_payment_counter
-// reset_payment_counter
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Query {
-    pub name: String,
-    pub arguments: HashMap<String, String>
+    #[serde(flatten)]
+    pub name: QueryName,
 }
 
 impl ToArma for Query {
     fn to_arma(&self) -> ArmaValue {
-        arma_value!({
-            "name": self.name,
-            "arguments": self.arguments
-        })
+        self.name.to_arma()
+    }
+}
+
+/// A single leaderboard participant. Serializes positionally as
+/// `[uid, name]` so SQF can read a `Leaderboard` entry without a lookup.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub uid: String,
+    pub name: String,
+}
+
+impl ToArma for Entry {
+    fn to_arma(&self) -> ArmaValue {
+        ArmaValue::Array(vec![self.uid.to_arma(), self.name.to_arma()])
+    }
+}
+
+/// Answers a `leaderboard*` query. `Ranking` is already sorted by the
+/// server (index 0 = first place); `Scores` carries a score alongside
+/// each entry and leaves sorting to the client.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Leaderboard {
+    Ranking(Vec<Entry>),
+    Scores(Vec<(Entry, i64)>),
+}
+
+impl Leaderboard {
+    /// Confirms every participant listed is a known player. Protects the
+    /// client from rendering an entry for a uid the caller can't resolve.
+    pub fn is_valid(&self, known_uids: &HashSet<String>) -> bool {
+        match self {
+            Leaderboard::Ranking(entries) => entries.iter().all(|entry| known_uids.contains(&entry.uid)),
+            Leaderboard::Scores(entries) => entries.iter().all(|(entry, _)| known_uids.contains(&entry.uid)),
+        }
+    }
+}
+
+impl ToArma for Leaderboard {
+    fn to_arma(&self) -> ArmaValue {
+        match self {
+            Leaderboard::Ranking(entries) => {
+                ArmaValue::Array(entries.iter().map(|entry| entry.to_arma()).collect())
+            }
+            Leaderboard::Scores(entries) => ArmaValue::Array(
+                entries
+                    .iter()
+                    .map(|(entry, score)| ArmaValue::Array(vec![entry.to_arma(), score.to_arma()]))
+                    .collect(),
+            ),
+        }
     }
 }
 
@@ -204,4 +481,141 @@ mod tests {
         let result = retrieve_data!(message, Test);
         assert_eq!(result.foo, String::from("testing"))
     }
+
+    #[test]
+    fn test_reward_to_arma() {
+        let reward = Reward {
+            player_poptabs: 100,
+            locker_poptabs: 50,
+            respect: 10,
+            items: HashMap::from([("exile_item_example".to_string(), 2i64)]),
+        };
+
+        let rendered = format!("{:?}", reward.to_arma());
+
+        assert!(rendered.contains("player_poptabs"));
+        assert!(rendered.contains("locker_poptabs"));
+        assert!(rendered.contains("respect"));
+        assert!(rendered.contains("items"));
+        assert!(rendered.contains("exile_item_example"));
+    }
+
+    #[test]
+    fn test_reward_serde_round_trip() {
+        let reward = Reward {
+            player_poptabs: 100,
+            locker_poptabs: 50,
+            respect: 10,
+            items: HashMap::from([("exile_item_example".to_string(), 2i64)]),
+        };
+
+        let json = serde_json::to_string(&reward).unwrap();
+        let deserialized: Reward = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, reward);
+    }
+
+    #[test]
+    fn test_leaderboard_is_valid() {
+        let known_uids = HashSet::from(["uid1".to_string()]);
+
+        let valid = Leaderboard::Ranking(vec![Entry { uid: "uid1".into(), name: "player1".into() }]);
+        assert!(valid.is_valid(&known_uids));
+
+        let invalid = Leaderboard::Scores(vec![(Entry { uid: "uid2".into(), name: "player2".into() }, 100)]);
+        assert!(!invalid.is_valid(&known_uids));
+    }
+
+    #[test]
+    fn test_probability_model_post_configure() {
+        let mut model = ProbabilityModel {
+            points: vec![
+                ProbabilityPoint { start_pity: 0, start_chance_percent: 1.0, increment_percent: 0.0 },
+                ProbabilityPoint { start_pity: 3, start_chance_percent: 50.0, increment_percent: 50.0 },
+            ],
+            clear_status_on_higher_rarity_pulled: true,
+            maximum_guarantee_pity: None,
+            ..Default::default()
+        };
+        model.post_configure();
+
+        assert_eq!(model.chance_at(0), 1.0);
+        assert_eq!(model.chance_at(2), 1.0);
+        assert_eq!(model.chance_at(3), 50.0);
+        assert_eq!(model.chance_at(4), 100.0);
+        assert_eq!(model.maximum_guarantee(), 4);
+    }
+
+    #[test]
+    fn test_reward_roll_to_arma() {
+        let mut probability = ProbabilityModel {
+            points: vec![ProbabilityPoint { start_pity: 0, start_chance_percent: 100.0, increment_percent: 0.0 }],
+            clear_status_on_higher_rarity_pulled: false,
+            maximum_guarantee_pity: None,
+            ..Default::default()
+        };
+        probability.post_configure();
+
+        let roll = RewardRoll { pity: 0, won: true, probability };
+        let rendered = format!("{:?}", roll.to_arma());
+
+        assert!(rendered.contains("pity"));
+        assert!(rendered.contains("won"));
+        assert!(rendered.contains("probability_percents"));
+        assert!(rendered.contains("maximum_guarantee"));
+    }
+
+    #[test]
+    fn test_probability_model_post_configure_non_converging() {
+        let mut model = ProbabilityModel {
+            points: vec![ProbabilityPoint { start_pity: 0, start_chance_percent: 1.0, increment_percent: 0.0 }],
+            clear_status_on_higher_rarity_pulled: false,
+            maximum_guarantee_pity: None,
+            ..Default::default()
+        };
+        model.post_configure();
+
+        assert_eq!(model.maximum_guarantee(), MAX_PITY_STEPS);
+        assert_eq!(model.chance_at(MAX_PITY_STEPS), 100.0);
+        assert_eq!(model.chance_at(MAX_PITY_STEPS - 1), 1.0);
+    }
+
+    #[test]
+    fn test_query_name_validate() {
+        let arguments = HashMap::from([("territory_id".to_string(), "1".to_string())]);
+        let query_name = QueryName::validate("territory", arguments).unwrap();
+        assert_eq!(query_name, QueryName::Territory(TerritoryArguments { territory_id: "1".into() }));
+
+        let missing = QueryName::validate("territory", HashMap::new());
+        assert_eq!(
+            missing,
+            Err(QueryValidationError::MissingArgument { query: "territory".into(), argument: "territory_id".into() })
+        );
+
+        let extra = HashMap::from([
+            ("territory_id".to_string(), "1".to_string()),
+            ("unexpected".to_string(), "1".to_string()),
+        ]);
+        assert!(matches!(
+            QueryName::validate("territory", extra),
+            Err(QueryValidationError::UnexpectedArgument { .. })
+        ));
+
+        assert_eq!(
+            QueryName::validate("unknown", HashMap::new()),
+            Err(QueryValidationError::UnknownQueryName("unknown".into()))
+        );
+    }
+
+    #[test]
+    fn test_query_serde_round_trip() {
+        let query = Query {
+            name: QueryName::Territory(TerritoryArguments { territory_id: "1".into() }),
+        };
+
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(json, r#"{"name":"territory","arguments":{"territory_id":"1"}}"#);
+
+        let deserialized: Query = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, query);
+    }
 }